@@ -0,0 +1,246 @@
+//! Grid geometry: tile positions, heights, and the spatial indices [`MapGeometry`] keeps over them.
+
+use std::{
+    collections::{HashMap, HashSet},
+    ops::{Add, Sub},
+};
+
+use bevy::prelude::{Component, Entity, Resource, Vec3};
+
+use crate::terrain::commands::{HeightOverride, HeightOverrideSource};
+
+/// The world-space footprint of a single tile along either hex axis.
+const TILE_WORLD_SIZE: f32 = 1.0;
+
+/// The number of tiles along one edge of a [`ChunkId`]'s footprint.
+///
+/// Chunks batch many tiles into a single merged mesh; see
+/// [`RebuildChunkMeshCommand`](crate::terrain::commands::RebuildChunkMeshCommand).
+const CHUNK_SIZE: i32 = 16;
+
+/// The axial coordinates of a single hex tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component)]
+pub(crate) struct TilePos {
+    /// The tile's position along the hex grid's `x` axis.
+    pub(crate) x: i32,
+    /// The tile's position along the hex grid's `y` axis.
+    pub(crate) y: i32,
+}
+
+impl TilePos {
+    /// Returns every [`TilePos`] within `radius` hex steps of `self`, including `self`.
+    ///
+    /// Does not filter by map bounds; callers that need only on-map tiles should filter the
+    /// result through [`MapGeometry::is_valid`].
+    pub(crate) fn hex_range(self, radius: u32) -> impl Iterator<Item = TilePos> {
+        let radius = radius as i32;
+        let center = self;
+
+        (-radius..=radius).flat_map(move |dx| {
+            let lo = (-radius).max(-dx - radius);
+            let hi = radius.min(-dx + radius);
+            (lo..=hi).map(move |dy| TilePos {
+                x: center.x + dx,
+                y: center.y + dy,
+            })
+        })
+    }
+
+    /// Computes this tile's world-space position, using its current base [`Height`].
+    pub(crate) fn into_world_pos(self, map_geometry: &MapGeometry) -> Vec3 {
+        let height = map_geometry.get_height(self).unwrap_or_default();
+        Vec3::new(
+            self.x as f32 * TILE_WORLD_SIZE,
+            height.into_world_pos(),
+            self.y as f32 * TILE_WORLD_SIZE,
+        )
+    }
+}
+
+/// The vertical position of a tile, in abstract height units.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Component)]
+pub(crate) struct Height(pub(crate) f32);
+
+impl Height {
+    /// Converts this height into a world-space `y` coordinate.
+    pub(crate) fn into_world_pos(self) -> f32 {
+        self.0
+    }
+}
+
+impl Add for Height {
+    type Output = Height;
+
+    fn add(self, rhs: Height) -> Height {
+        Height(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Height {
+    type Output = Height;
+
+    fn sub(self, rhs: Height) -> Height {
+        Height(self.0 - rhs.0)
+    }
+}
+
+/// Identifies a square batch of tiles whose meshes are merged into one draw call.
+///
+/// See [`MapGeometry::chunk_id`] and [`RebuildChunkMeshCommand`](crate::terrain::commands::RebuildChunkMeshCommand).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct ChunkId {
+    /// The chunk's position along the hex grid's `x` axis, in units of [`CHUNK_SIZE`] tiles.
+    cx: i32,
+    /// The chunk's position along the hex grid's `y` axis, in units of [`CHUNK_SIZE`] tiles.
+    cy: i32,
+}
+
+/// The spatial indices over the map's tiles: what terrain is where, how tall it is, and which
+/// chunk's merged mesh needs rebuilding.
+#[derive(Resource, Debug, Default)]
+pub(crate) struct MapGeometry {
+    /// Every tile position that exists on the map.
+    valid_tile_positions: HashSet<TilePos>,
+    /// The terrain entity occupying each tile.
+    terrain_index: HashMap<TilePos, Entity>,
+    /// The ghost terrain entity previewed at each tile, if any.
+    ghost_index: HashMap<TilePos, Entity>,
+    /// The base [`Height`] of each tile.
+    height_index: HashMap<TilePos, Height>,
+    /// The stack of [`HeightOverride`]s applied to each tile, keyed by who pushed them.
+    height_overrides: HashMap<TilePos, Vec<(HeightOverrideSource, HeightOverride)>>,
+    /// The merged mesh entity currently rendering each chunk, if it has been built.
+    chunk_mesh_index: HashMap<ChunkId, Entity>,
+    /// Chunks whose mesh no longer reflects the current state of their tiles.
+    dirty_chunks: HashSet<ChunkId>,
+}
+
+impl MapGeometry {
+    /// Returns whether `tile_pos` is within the bounds of the map.
+    pub(crate) fn is_valid(&self, tile_pos: TilePos) -> bool {
+        self.valid_tile_positions.contains(&tile_pos)
+    }
+
+    /// Returns every [`TilePos`] that exists on the map.
+    pub(crate) fn valid_tile_positions(&self) -> impl Iterator<Item = TilePos> + '_ {
+        self.valid_tile_positions.iter().copied()
+    }
+
+    /// Returns the terrain entity at `tile_pos`, if any.
+    pub(crate) fn get_terrain(&self, tile_pos: TilePos) -> Option<Entity> {
+        self.terrain_index.get(&tile_pos).copied()
+    }
+
+    /// Records `terrain_entity` as occupying `tile_pos`, overwriting any previous occupant.
+    pub(crate) fn add_terrain(&mut self, tile_pos: TilePos, terrain_entity: Entity) {
+        self.valid_tile_positions.insert(tile_pos);
+        self.terrain_index.insert(tile_pos, terrain_entity);
+    }
+
+    /// Returns the ghost terrain entity previewed at `tile_pos`, if any.
+    pub(crate) fn get_ghost_terrain(&self, tile_pos: TilePos) -> Option<Entity> {
+        self.ghost_index.get(&tile_pos).copied()
+    }
+
+    /// Records `ghost_entity` as the ghost previewed at `tile_pos`.
+    pub(crate) fn add_ghost_terrain(&mut self, ghost_entity: Entity, tile_pos: TilePos) {
+        self.ghost_index.insert(tile_pos, ghost_entity);
+    }
+
+    /// Clears the ghost previewed at `tile_pos`, returning its entity if there was one.
+    pub(crate) fn remove_ghost_terrain(&mut self, tile_pos: TilePos) -> Option<Entity> {
+        self.ghost_index.remove(&tile_pos)
+    }
+
+    /// Returns the base [`Height`] of `tile_pos`, if it is a valid tile.
+    pub(crate) fn get_height(&self, tile_pos: TilePos) -> Option<Height> {
+        self.height_index.get(&tile_pos).copied()
+    }
+
+    /// Sets the base [`Height`] of `tile_pos`.
+    pub(crate) fn update_height(&mut self, tile_pos: TilePos, height: Height) {
+        self.height_index.insert(tile_pos, height);
+    }
+
+    /// Returns the rendered height of `tile_pos`: the topmost [`HeightOverride`] on the tile, if
+    /// any, otherwise its base [`Height`].
+    pub(crate) fn effective_height(&self, tile_pos: TilePos) -> Height {
+        let base_height = self.get_height(tile_pos).unwrap_or_default();
+
+        match self
+            .height_overrides
+            .get(&tile_pos)
+            .and_then(|stack| stack.last())
+        {
+            Some((_, HeightOverride::Absolute(height))) => *height,
+            Some((_, HeightOverride::Relative(offset))) => base_height + *offset,
+            None => base_height,
+        }
+    }
+
+    /// Pushes `height_override` onto `tile_pos`'s override stack, replacing any existing entry
+    /// from the same `source`.
+    pub(crate) fn push_height_override(
+        &mut self,
+        tile_pos: TilePos,
+        source: HeightOverrideSource,
+        height_override: HeightOverride,
+    ) {
+        let stack = self.height_overrides.entry(tile_pos).or_default();
+        stack.retain(|(existing_source, _)| *existing_source != source);
+        stack.push((source, height_override));
+    }
+
+    /// Removes `source`'s [`HeightOverride`] from `tile_pos`'s stack, if present.
+    pub(crate) fn remove_height_override(
+        &mut self,
+        tile_pos: TilePos,
+        source: HeightOverrideSource,
+    ) {
+        if let Some(stack) = self.height_overrides.get_mut(&tile_pos) {
+            stack.retain(|(existing_source, _)| *existing_source != source);
+            if stack.is_empty() {
+                self.height_overrides.remove(&tile_pos);
+            }
+        }
+    }
+
+    /// Returns the [`ChunkId`] that `tile_pos` belongs to.
+    pub(crate) fn chunk_id(&self, tile_pos: TilePos) -> ChunkId {
+        ChunkId {
+            cx: tile_pos.x.div_euclid(CHUNK_SIZE),
+            cy: tile_pos.y.div_euclid(CHUNK_SIZE),
+        }
+    }
+
+    /// Returns every valid [`TilePos`] that belongs to `chunk_id`.
+    pub(crate) fn tiles_in_chunk(&self, chunk_id: ChunkId) -> impl Iterator<Item = TilePos> + '_ {
+        self.valid_tile_positions()
+            .filter(move |tile_pos| self.chunk_id(*tile_pos) == chunk_id)
+    }
+
+    /// Marks `chunk_id` as needing its merged mesh rebuilt.
+    pub(crate) fn mark_chunk_dirty(&mut self, chunk_id: ChunkId) {
+        self.dirty_chunks.insert(chunk_id);
+    }
+
+    /// Clears the dirty flag on `chunk_id`.
+    pub(crate) fn clear_chunk_dirty(&mut self, chunk_id: ChunkId) {
+        self.dirty_chunks.remove(&chunk_id);
+    }
+
+    /// Returns every chunk currently marked dirty.
+    pub(crate) fn dirty_chunks(&self) -> impl Iterator<Item = ChunkId> + '_ {
+        self.dirty_chunks.iter().copied()
+    }
+
+    /// Returns the merged mesh entity currently rendering `chunk_id`, removing it from the index.
+    pub(crate) fn remove_chunk_mesh_entity(&mut self, chunk_id: ChunkId) -> Option<Entity> {
+        self.chunk_mesh_index.remove(&chunk_id)
+    }
+
+    /// Records `chunk_entity` as the merged mesh entity currently rendering `chunk_id`.
+    pub(crate) fn set_chunk_mesh_entity(&mut self, chunk_id: ChunkId, chunk_entity: Entity) {
+        self.chunk_mesh_index.insert(chunk_id, chunk_entity);
+    }
+}
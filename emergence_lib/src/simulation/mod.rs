@@ -0,0 +1,3 @@
+//! The simulated world's core data: tile positions, heights, and spatial indices.
+
+pub(crate) mod geometry;
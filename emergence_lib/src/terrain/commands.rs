@@ -3,11 +3,13 @@
 use bevy::{
     ecs::system::{Command, SystemState},
     prelude::{
-        BuildWorldChildren, Commands, DespawnRecursiveExt, Handle, PbrBundle, Query, Res, ResMut,
-        Transform, Vec3, Visibility, World,
+        Assets, BuildWorldChildren, Children, Commands, DespawnRecursiveExt, Handle, Mesh,
+        PbrBundle, Query, Res, ResMut, Resource, Transform, Vec3, Visibility, World,
     },
+    render::mesh::{Indices, VertexAttributeValues},
     scene::Scene,
 };
+use noise::{Fbm, MultiFractal, NoiseFn, Perlin};
 
 use crate::{
     asset_management::manifest::Id,
@@ -17,12 +19,103 @@ use crate::{
         zoning::Zoning,
     },
     graphics::InheritedMaterial,
-    simulation::geometry::{Height, MapGeometry, TilePos},
+    simulation::geometry::{ChunkId, Height, MapGeometry, TilePos},
     terrain::{terrain_assets::TerrainHandles, terrain_manifest::Terrain},
 };
 
 use super::TerrainBundle;
 
+/// Bounds on how far terrain can be raised or lowered.
+///
+/// Both [`ApplyTerraformingCommand`] and the terraforming ghost/preview clamp to this range, so a
+/// tile can never be dug or built past it. This lets map designers define bowl-shaped or
+/// plateau-bounded worlds, rather than relying on players to self-regulate.
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub(crate) struct HeightLimits {
+    /// The lowest [`Height`] that terrain can be lowered to.
+    pub(crate) min: Height,
+    /// The highest [`Height`] that terrain can be raised to.
+    pub(crate) max: Height,
+}
+
+impl Default for HeightLimits {
+    fn default() -> Self {
+        HeightLimits {
+            min: Height(-40.),
+            max: Height(100.),
+        }
+    }
+}
+
+impl HeightLimits {
+    /// Clamps `height` to lie within `[self.min, self.max]`.
+    fn clamp(&self, height: Height) -> Height {
+        if height < self.min {
+            self.min
+        } else if height > self.max {
+            self.max
+        } else {
+            height
+        }
+    }
+}
+
+/// The [`Id<Terrain>`] chosen for each elevation band by [`generate_terrain_from_heightmap`](TerrainCommandsExt::generate_terrain_from_heightmap).
+///
+/// Tiles are assigned a terrain type by comparing their normalized elevation against
+/// `low_cutoff` and `high_cutoff`: below `low_cutoff` gets `low`, above `high_cutoff` gets
+/// `high`, and everything in between gets `mid`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ElevationTerrain {
+    /// The terrain used for low-lying tiles, e.g. sediment.
+    pub(crate) low: Id<Terrain>,
+    /// The terrain used for tiles at a middling elevation, e.g. loam.
+    pub(crate) mid: Id<Terrain>,
+    /// The terrain used for high tiles, e.g. rocky outcrops.
+    pub(crate) high: Id<Terrain>,
+    /// The normalized elevation (in `[0, 1]`) below which [`Self::low`] is used.
+    pub(crate) low_cutoff: f64,
+    /// The normalized elevation (in `[0, 1]`) above which [`Self::high`] is used.
+    pub(crate) high_cutoff: f64,
+}
+
+impl ElevationTerrain {
+    /// Chooses the terrain type for a normalized elevation in `[0, 1]`.
+    fn terrain_for(&self, normalized_elevation: f64) -> Id<Terrain> {
+        if normalized_elevation < self.low_cutoff {
+            self.low
+        } else if normalized_elevation > self.high_cutoff {
+            self.high
+        } else {
+            self.mid
+        }
+    }
+}
+
+/// Identifies whoever pushed a [`HeightOverride`], so that a system can remove exactly the entry
+/// it pushed without disturbing anyone else's.
+///
+/// For example, a road system might use `HeightOverrideSource(road_entity.index())` so that
+/// removing a road only ever un-levels the ground it itself leveled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct HeightOverrideSource(pub(crate) u32);
+
+/// A temporary override of a tile's rendered height, which does not touch its underlying
+/// [`Height`].
+///
+/// [`MapGeometry`] stores a per-tile stack of these, keyed by [`HeightOverrideSource`]; the
+/// topmost override (if any) sets the *effective* height, which the chunk's merged column/topper
+/// mesh and the tile's overlay are rendered at. [`TilePos::into_world_pos`] and the tile entity
+/// itself still use the base [`Height`]; overrides do not move them. [`ApplyTerraformingCommand`]
+/// is unaffected, and always keeps editing the tile's real, underlying [`Height`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum HeightOverride {
+    /// Render the tile as if it were exactly this height.
+    Absolute(Height),
+    /// Render the tile as if it were this much higher (or, if negative, lower) than its base height.
+    Relative(Height),
+}
+
 /// An extension trait for [`Commands`] for working with terrain.
 pub(crate) trait TerrainCommandsExt {
     /// Spawns a new terrain tile.
@@ -55,6 +148,48 @@ pub(crate) trait TerrainCommandsExt {
 
     /// Applies the given `terraforming_action` to the terrain at `tile_pos`.
     fn apply_terraforming_action(&mut self, tile_pos: TilePos, action: TerraformingAction);
+
+    /// Applies the given `action` to every tile within `radius` hex steps of `center`.
+    ///
+    /// Out-of-bounds tiles are skipped. Tiles already at a [`HeightLimits`] bound are still
+    /// visited, but `ApplyTerraformingCommand` treats a `Raise`/`Lower` past the bound as a no-op.
+    fn apply_terraforming_brush(
+        &mut self,
+        center: TilePos,
+        radius: u32,
+        action: TerraformingAction,
+    );
+
+    /// Spawns a preview of `terraforming_action` on every tile within `radius` hex steps of
+    /// `center`, so the player can see the whole footprint of a brush before committing to it.
+    fn spawn_preview_terrain_brush(
+        &mut self,
+        center: TilePos,
+        radius: u32,
+        terrain_id: Id<Terrain>,
+        terraforming_action: TerraformingAction,
+    );
+
+    /// Fills the whole map with procedurally generated terrain, sampled from fractal noise.
+    ///
+    /// The same `seed` always produces the same terrain, so generated maps are reproducible.
+    fn generate_terrain_from_heightmap(&mut self, seed: u32, elevation_terrain: ElevationTerrain);
+
+    /// Pushes a [`HeightOverride`] onto `tile_pos`, without affecting its underlying [`Height`].
+    ///
+    /// Replaces any existing override from the same `source`.
+    fn push_height_override(
+        &mut self,
+        tile_pos: TilePos,
+        source: HeightOverrideSource,
+        height_override: HeightOverride,
+    );
+
+    /// Removes the [`HeightOverride`] that `source` previously pushed onto `tile_pos`, if any.
+    ///
+    /// The tile reverts to the next override below it in the stack, or to its base [`Height`] if
+    /// this was the only one.
+    fn remove_height_override(&mut self, tile_pos: TilePos, source: HeightOverrideSource);
 }
 
 impl<'w, 's> TerrainCommandsExt for Commands<'w, 's> {
@@ -108,14 +243,76 @@ impl<'w, 's> TerrainCommandsExt for Commands<'w, 's> {
             terraforming_action,
         });
     }
+
+    fn apply_terraforming_brush(
+        &mut self,
+        center: TilePos,
+        radius: u32,
+        terraforming_action: TerraformingAction,
+    ) {
+        self.add(ApplyTerraformingBrushCommand {
+            center,
+            radius,
+            terraforming_action,
+        });
+    }
+
+    fn spawn_preview_terrain_brush(
+        &mut self,
+        center: TilePos,
+        radius: u32,
+        terrain_id: Id<Terrain>,
+        terraforming_action: TerraformingAction,
+    ) {
+        self.add(SpawnTerrainPreviewBrushCommand {
+            center,
+            radius,
+            terrain_id,
+            terraforming_action,
+        });
+    }
+
+    fn generate_terrain_from_heightmap(&mut self, seed: u32, elevation_terrain: ElevationTerrain) {
+        self.add(GenerateTerrainFromHeightmapCommand {
+            seed,
+            elevation_terrain,
+        });
+    }
+
+    fn push_height_override(
+        &mut self,
+        tile_pos: TilePos,
+        source: HeightOverrideSource,
+        height_override: HeightOverride,
+    ) {
+        self.add(PushHeightOverrideCommand {
+            tile_pos,
+            source,
+            height_override,
+        });
+    }
+
+    fn remove_height_override(&mut self, tile_pos: TilePos, source: HeightOverrideSource) {
+        self.add(RemoveHeightOverrideCommand { tile_pos, source });
+    }
+}
+
+/// Returns every [`TilePos`] within `radius` hex steps of `center` that exists on the map.
+fn tiles_in_brush(center: TilePos, radius: u32, map_geometry: &MapGeometry) -> Vec<TilePos> {
+    center
+        .hex_range(radius)
+        .filter(|tile_pos| map_geometry.is_valid(*tile_pos))
+        .collect()
 }
 
 /// Constructs a new [`Terrain`] entity.
 ///
 /// The order of the chidlren *must* be:
-/// 0: column
-/// 1: overlay
-/// 2: scene root
+/// 0: overlay
+/// 1: scene root
+///
+/// The column/topper mesh that used to be rendered per-tile as a third child is now batched into
+/// the owning chunk's merged mesh instead; see [`RebuildChunkMeshCommand`].
 pub(crate) struct SpawnTerrainCommand {
     /// The position to spawn the tile
     pub(crate) tile_pos: TilePos,
@@ -134,6 +331,7 @@ impl Command for SpawnTerrainCommand {
 
         // Store the height, so it can be used below
         map_geometry.update_height(self.tile_pos, self.height);
+        let chunk_id = map_geometry.chunk_id(self.tile_pos);
 
         // Drop the borrow so the borrow checker is happy
         let map_geometry = world.resource::<MapGeometry>();
@@ -149,17 +347,14 @@ impl Command for SpawnTerrainCommand {
             ))
             .id();
 
-        // Spawn the column as the 0th child of the tile entity
-        // The scene bundle will be added as the first child
-        let handles = world.resource::<TerrainHandles>();
-        let column_bundle = PbrBundle {
-            mesh: handles.column_mesh.clone_weak(),
-            material: handles.column_material.clone_weak(),
-            ..Default::default()
-        };
-
-        let hex_column = world.spawn(column_bundle).id();
-        world.entity_mut(terrain_entity).add_child(hex_column);
+        let map_geometry = world.resource::<MapGeometry>();
+        // The parent `terrain_entity` sits at the tile's base height, so the overlay (which is
+        // parented to it) needs its own local offset to visually track the *effective* height
+        // instead, keeping it aligned with the batched chunk mesh under an active override.
+        let overlay_height_offset = map_geometry
+            .effective_height(self.tile_pos)
+            .into_world_pos()
+            - self.height.into_world_pos();
 
         let handles = world.resource::<TerrainHandles>();
         /// Makes the overlays ever so slightly larger than their base to avoid z-fighting.
@@ -170,11 +365,15 @@ impl Command for SpawnTerrainCommand {
         let overlay_bundle = PbrBundle {
             mesh: handles.topper_mesh.clone_weak(),
             visibility: Visibility::Hidden,
-            transform: Transform::from_scale(Vec3 {
-                x: OVERLAY_OVERSIZE_SCALE,
-                y: OVERLAY_OVERSIZE_SCALE,
-                z: OVERLAY_OVERSIZE_SCALE,
-            }),
+            transform: Transform {
+                translation: Vec3::new(0., overlay_height_offset, 0.),
+                scale: Vec3 {
+                    x: OVERLAY_OVERSIZE_SCALE,
+                    y: OVERLAY_OVERSIZE_SCALE,
+                    z: OVERLAY_OVERSIZE_SCALE,
+                },
+                ..Default::default()
+            },
             ..Default::default()
         };
         let overlay = world.spawn(overlay_bundle).id();
@@ -183,6 +382,11 @@ impl Command for SpawnTerrainCommand {
         // Update the index of what terrain is where
         let mut map_geometry = world.resource_mut::<MapGeometry>();
         map_geometry.add_terrain(self.tile_pos, terrain_entity);
+        // Mark the chunk dirty rather than rebuilding its mesh right away: spawning many tiles in
+        // the same chunk back-to-back (e.g. during map generation) would otherwise re-merge that
+        // chunk's mesh once per tile. `rebuild_dirty_chunk_meshes` drains dirty chunks once per
+        // frame, so the chunk is rebuilt exactly once no matter how many of its tiles changed.
+        map_geometry.mark_chunk_dirty(chunk_id);
     }
 }
 
@@ -228,20 +432,43 @@ impl Command for SpawnTerrainGhostCommand {
         let ghost_material = ghost_handles.get_material(self.ghost_kind);
 
         let inherited_material = InheritedMaterial(ghost_material);
+        let height_limits = world.resource::<HeightLimits>();
         let current_height = map_geometry.get_height(self.tile_pos).unwrap();
+        // Mirrors `ApplyTerraformingCommand`: a `Raise`/`Lower` that would pass a bound leaves the
+        // height unchanged, while a `SetHeight` target is clamped into range.
         let new_height = match self.terraforming_action {
-            TerraformingAction::Raise => current_height + Height(1.),
-            TerraformingAction::Lower => current_height - Height(1.),
+            TerraformingAction::Raise if current_height + Height(1.) <= height_limits.max => {
+                current_height + Height(1.)
+            }
+            TerraformingAction::Lower if current_height - Height(1.) >= height_limits.min => {
+                current_height - Height(1.)
+            }
+            TerraformingAction::SetHeight(target_height) => height_limits.clamp(target_height),
             _ => current_height,
         };
 
         let mut world_pos = self.tile_pos.into_world_pos(map_geometry);
         world_pos.y = new_height.into_world_pos();
 
+        // A `Raise`/`Lower` that's pinned against a `HeightLimits` bound won't actually move any
+        // earth once `ApplyTerraformingCommand` treats it as a no-op, so the ghost shouldn't
+        // charge for it either. Re-expressing it as a `SetHeight` to the unchanged height reuses
+        // the zero-displacement cost a true no-op `SetHeight` already gets. A `SetHeight` is
+        // costed against its clamped target, since that is as far as the tile will move.
+        let effective_action = match self.terraforming_action {
+            TerraformingAction::Raise | TerraformingAction::Lower if new_height == current_height => {
+                TerraformingAction::SetHeight(current_height)
+            }
+            TerraformingAction::SetHeight(_) => TerraformingAction::SetHeight(new_height),
+            action => action,
+        };
+
         match self.ghost_kind {
             GhostKind::Ghost => {
-                let input_inventory = self.terraforming_action.input_inventory();
-                let output_inventory = self.terraforming_action.output_inventory();
+                // `SetHeight`'s cost scales with how much earth the move actually displaces, so
+                // the action needs the tile's current height alongside its own target height.
+                let input_inventory = effective_action.input_inventory(current_height);
+                let output_inventory = effective_action.output_inventory(current_height);
 
                 let ghost_entity = world
                     .spawn(GhostTerrainBundle::new(
@@ -309,6 +536,7 @@ impl Command for ApplyTerraformingCommand {
         let mut system_state = SystemState::<(
             ResMut<MapGeometry>,
             Res<TerrainHandles>,
+            Res<HeightLimits>,
             Query<(
                 &mut Id<Terrain>,
                 &mut Zoning,
@@ -317,16 +545,35 @@ impl Command for ApplyTerraformingCommand {
             )>,
         )>::new(world);
 
-        let (mut map_geometry, terrain_handles, mut terrain_query) = system_state.get_mut(world);
+        let (mut map_geometry, terrain_handles, height_limits, mut terrain_query) =
+            system_state.get_mut(world);
 
         let terrain_entity = map_geometry.get_terrain(self.tile_pos).unwrap();
 
         let (mut current_terrain_id, mut zoning, mut height, mut scene_handle) =
             terrain_query.get_mut(terrain_entity).unwrap();
+        let height_before = *height;
 
         match self.terraforming_action {
-            TerraformingAction::Raise => height.raise(),
-            TerraformingAction::Lower => height.lower(),
+            TerraformingAction::Raise => {
+                // Raising past the ceiling is a no-op, rather than silently clamping.
+                let raised = *height + Height(1.);
+                if raised <= height_limits.max {
+                    *height = raised;
+                }
+            }
+            TerraformingAction::Lower => {
+                // Lowering past the floor is a no-op, rather than silently clamping.
+                let lowered = *height - Height(1.);
+                if lowered >= height_limits.min {
+                    *height = lowered;
+                }
+            }
+            TerraformingAction::SetHeight(target_height) => {
+                // Unlike `Raise`/`Lower`, this sets the absolute height directly; it's still
+                // subject to the same bounds so a brush can't be used to sidestep them.
+                *height = height_limits.clamp(target_height);
+            }
             TerraformingAction::Change(changed_terrain_id) => {
                 *current_terrain_id = changed_terrain_id;
             }
@@ -343,5 +590,317 @@ impl Command for ApplyTerraformingCommand {
 
         map_geometry.update_height(self.tile_pos, *height);
         *zoning = Zoning::None;
+
+        // Raising or lowering a tile changes the shape of its chunk's merged mesh, so that chunk
+        // (and only that chunk) needs to be rebuilt. Marking it dirty, rather than rebuilding
+        // right away, lets a brush spanning many tiles in the same chunk rebuild that chunk once
+        // instead of once per tile; see `rebuild_dirty_chunk_meshes`. A `Change` of terrain type,
+        // or a `Raise`/`Lower` that no-op'd against a `HeightLimits` bound, doesn't change the
+        // chunk's geometry, so skip the rebuild entirely rather than forcing one for no reason.
+        if *height != height_before {
+            let chunk_id = map_geometry.chunk_id(self.tile_pos);
+            map_geometry.mark_chunk_dirty(chunk_id);
+        }
+    }
+}
+
+/// A [`Command`] used to apply a [`TerraformingAction`] to every tile within a radius of a
+/// center tile via [`TerrainCommandsExt::apply_terraforming_brush`].
+struct ApplyTerraformingBrushCommand {
+    /// The tile position at the center of the brush.
+    center: TilePos,
+    /// The radius of the brush, in hex steps.
+    radius: u32,
+    /// The action to apply to each tile within the brush.
+    terraforming_action: TerraformingAction,
+}
+
+impl Command for ApplyTerraformingBrushCommand {
+    fn write(self, world: &mut World) {
+        let map_geometry = world.resource::<MapGeometry>();
+        let tile_positions = tiles_in_brush(self.center, self.radius, map_geometry);
+
+        for tile_pos in tile_positions {
+            ApplyTerraformingCommand {
+                tile_pos,
+                terraforming_action: self.terraforming_action,
+            }
+            .write(world);
+        }
+    }
+}
+
+/// A [`Command`] used to spawn a preview of a [`TerraformingAction`] on every tile within a
+/// radius of a center tile via [`TerrainCommandsExt::spawn_preview_terrain_brush`].
+struct SpawnTerrainPreviewBrushCommand {
+    /// The tile position at the center of the brush.
+    center: TilePos,
+    /// The radius of the brush, in hex steps.
+    radius: u32,
+    /// The terrain type that the preview represents.
+    terrain_id: Id<Terrain>,
+    /// The action that the preview represents.
+    terraforming_action: TerraformingAction,
+}
+
+impl Command for SpawnTerrainPreviewBrushCommand {
+    fn write(self, world: &mut World) {
+        let map_geometry = world.resource::<MapGeometry>();
+        let tile_positions = tiles_in_brush(self.center, self.radius, map_geometry);
+
+        for tile_pos in tile_positions {
+            SpawnTerrainGhostCommand {
+                tile_pos,
+                terrain_id: self.terrain_id,
+                terraforming_action: self.terraforming_action,
+                ghost_kind: GhostKind::Preview,
+            }
+            .write(world);
+        }
+    }
+}
+
+/// The number of octaves of noise summed together when generating a heightmap.
+///
+/// More octaves add finer detail at the cost of generation time.
+const HEIGHTMAP_OCTAVES: usize = 4;
+
+/// A [`Command`] used to fill the whole map with procedurally generated terrain via
+/// [`TerrainCommandsExt::generate_terrain_from_heightmap`].
+struct GenerateTerrainFromHeightmapCommand {
+    /// The seed for the noise function; the same seed always produces the same terrain.
+    seed: u32,
+    /// The terrain type chosen for each elevation band.
+    elevation_terrain: ElevationTerrain,
+}
+
+impl Command for GenerateTerrainFromHeightmapCommand {
+    fn write(self, world: &mut World) {
+        let map_geometry = world.resource::<MapGeometry>();
+        let height_limits = *world.resource::<HeightLimits>();
+        let tile_positions = map_geometry.valid_tile_positions().collect::<Vec<_>>();
+
+        let noise = Fbm::<Perlin>::new(self.seed).set_octaves(HEIGHTMAP_OCTAVES);
+
+        for tile_pos in tile_positions {
+            let map_geometry = world.resource::<MapGeometry>();
+            let world_pos = tile_pos.into_world_pos(map_geometry);
+
+            // `Fbm::get` is nominally `[-1, 1]`, but summing multiple octaves isn't guaranteed to
+            // stay within that range, so clamp before renormalizing to `[0, 1]`.
+            let raw_elevation = noise
+                .get([world_pos.x as f64, world_pos.z as f64])
+                .clamp(-1., 1.);
+            let normalized_elevation = (raw_elevation + 1.) / 2.;
+
+            let height_range = height_limits.max.0 - height_limits.min.0;
+            let height = height_limits.clamp(Height(
+                height_limits.min.0 + height_range * normalized_elevation as f32,
+            ));
+            let terrain_id = self.elevation_terrain.terrain_for(normalized_elevation);
+
+            SpawnTerrainCommand {
+                tile_pos,
+                height,
+                terrain_id,
+            }
+            .write(world);
+        }
+    }
+}
+
+/// A [`Command`] that rebuilds the single merged column/topper mesh for a chunk.
+///
+/// Issued by [`rebuild_dirty_chunk_meshes`] for every chunk marked dirty since the last rebuild,
+/// so the chunk's mesh reflects the current state of its tiles without needing one draw call per
+/// tile.
+pub(crate) struct RebuildChunkMeshCommand {
+    /// The chunk whose mesh should be rebuilt.
+    pub(crate) chunk_id: ChunkId,
+}
+
+impl Command for RebuildChunkMeshCommand {
+    fn write(self, world: &mut World) {
+        let mut map_geometry = world.resource_mut::<MapGeometry>();
+        let old_chunk_entity = map_geometry.remove_chunk_mesh_entity(self.chunk_id);
+        map_geometry.clear_chunk_dirty(self.chunk_id);
+
+        // Drop the borrow above before touching `world` again; despawn the chunk's previous
+        // merged mesh entity, if any, now that it's about to be replaced.
+        if let Some(old_chunk_entity) = old_chunk_entity {
+            world.entity_mut(old_chunk_entity).despawn_recursive();
+        }
+
+        let map_geometry = world.resource::<MapGeometry>();
+        let tile_transforms: Vec<Transform> = map_geometry
+            .tiles_in_chunk(self.chunk_id)
+            .map(|tile_pos| {
+                // Consult the effective height, not just the base height, so that e.g. a road's
+                // height override is reflected in the chunk's rendered geometry.
+                let mut world_pos = tile_pos.into_world_pos(map_geometry);
+                world_pos.y = map_geometry.effective_height(tile_pos).into_world_pos();
+                Transform::from_translation(world_pos)
+            })
+            .collect();
+
+        let terrain_handles = world.resource::<TerrainHandles>();
+        let column_mesh_handle = terrain_handles.column_mesh.clone_weak();
+        let topper_mesh_handle = terrain_handles.topper_mesh.clone_weak();
+        let column_material = terrain_handles.column_material.clone_weak();
+
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let merged_mesh = {
+            let column_mesh = meshes.get(&column_mesh_handle).unwrap();
+            let topper_mesh = meshes.get(&topper_mesh_handle).unwrap();
+            merge_tile_meshes(&[column_mesh, topper_mesh], &tile_transforms)
+        };
+        let merged_mesh_handle = meshes.add(merged_mesh);
+
+        let chunk_entity = world
+            .spawn(PbrBundle {
+                mesh: merged_mesh_handle,
+                material: column_material,
+                ..Default::default()
+            })
+            .id();
+
+        let mut map_geometry = world.resource_mut::<MapGeometry>();
+        map_geometry.set_chunk_mesh_entity(self.chunk_id, chunk_entity);
+    }
+}
+
+/// Combines one copy of every mesh in `source_meshes` per transform in `tile_transforms` into a
+/// single merged [`Mesh`], baking each tile's world position into its vertex data.
+///
+/// This trades thousands of tiny per-tile draw calls for one draw call per chunk.
+fn merge_tile_meshes(source_meshes: &[&Mesh], tile_transforms: &[Transform]) -> Mesh {
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+
+    for tile_transform in tile_transforms {
+        for source_mesh in source_meshes {
+            let Some(VertexAttributeValues::Float32x3(source_positions)) =
+                source_mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+            else {
+                panic!("Terrain meshes must have Float32x3 positions");
+            };
+            let Some(VertexAttributeValues::Float32x3(source_normals)) =
+                source_mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+            else {
+                panic!("Terrain meshes must have Float32x3 normals");
+            };
+            let Some(VertexAttributeValues::Float32x2(source_uvs)) =
+                source_mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+            else {
+                panic!("Terrain meshes must have Float32x2 UVs");
+            };
+            let Some(Indices::U32(source_indices)) = source_mesh.indices() else {
+                panic!("Terrain meshes must have u32 indices");
+            };
+
+            let vertex_offset = positions.len() as u32;
+
+            for &[x, y, z] in source_positions {
+                let transformed = tile_transform.transform_point(Vec3::new(x, y, z));
+                positions.push([transformed.x, transformed.y, transformed.z]);
+            }
+
+            for &[x, y, z] in source_normals {
+                let rotated = tile_transform.rotation * Vec3::new(x, y, z);
+                normals.push([rotated.x, rotated.y, rotated.z]);
+            }
+
+            uvs.extend_from_slice(source_uvs);
+            indices.extend(source_indices.iter().map(|index| index + vertex_offset));
+        }
+    }
+
+    let mut merged = Mesh::new(source_meshes[0].primitive_topology());
+    merged.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    merged.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    merged.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    merged.set_indices(Some(Indices::U32(indices)));
+    merged
+}
+
+/// A [`Command`] used to push a [`HeightOverride`] via [`TerrainCommandsExt::push_height_override`].
+struct PushHeightOverrideCommand {
+    /// The tile position to override the rendered height of.
+    tile_pos: TilePos,
+    /// Identifies the caller that pushed this override, so it alone can later remove it.
+    source: HeightOverrideSource,
+    /// The override to push.
+    height_override: HeightOverride,
+}
+
+impl Command for PushHeightOverrideCommand {
+    fn write(self, world: &mut World) {
+        let mut map_geometry = world.resource_mut::<MapGeometry>();
+        map_geometry.push_height_override(self.tile_pos, self.source, self.height_override);
+        // Mark dirty rather than rebuilding immediately; see `rebuild_dirty_chunk_meshes`.
+        let chunk_id = map_geometry.chunk_id(self.tile_pos);
+        map_geometry.mark_chunk_dirty(chunk_id);
+
+        sync_overlay_height(world, self.tile_pos);
+    }
+}
+
+/// A [`Command`] used to remove a [`HeightOverride`] via [`TerrainCommandsExt::remove_height_override`].
+struct RemoveHeightOverrideCommand {
+    /// The tile position to stop overriding the rendered height of.
+    tile_pos: TilePos,
+    /// Identifies which caller's override to remove.
+    source: HeightOverrideSource,
+}
+
+impl Command for RemoveHeightOverrideCommand {
+    fn write(self, world: &mut World) {
+        let mut map_geometry = world.resource_mut::<MapGeometry>();
+        map_geometry.remove_height_override(self.tile_pos, self.source);
+        // Mark dirty rather than rebuilding immediately; see `rebuild_dirty_chunk_meshes`.
+        let chunk_id = map_geometry.chunk_id(self.tile_pos);
+        map_geometry.mark_chunk_dirty(chunk_id);
+
+        sync_overlay_height(world, self.tile_pos);
+    }
+}
+
+/// Realigns `tile_pos`'s selection/highlight overlay with its current effective height.
+///
+/// The overlay is parented to the tile entity, which always sits at the tile's base height, so it
+/// needs its own local offset to stay visually aligned with the batched chunk mesh whenever a
+/// [`HeightOverride`] is pushed or removed on top of an already-spawned tile.
+fn sync_overlay_height(world: &mut World, tile_pos: TilePos) {
+    let map_geometry = world.resource::<MapGeometry>();
+    let Some(terrain_entity) = map_geometry.get_terrain(tile_pos) else {
+        return;
+    };
+    let overlay_height_offset = map_geometry.effective_height(tile_pos).into_world_pos()
+        - map_geometry.get_height(tile_pos).unwrap().into_world_pos();
+
+    let overlay_entity = world
+        .get::<Children>(terrain_entity)
+        .and_then(|children| children.first().copied());
+
+    if let Some(overlay_entity) = overlay_entity {
+        if let Some(mut transform) = world.get_mut::<Transform>(overlay_entity) {
+            transform.translation.y = overlay_height_offset;
+        }
+    }
+}
+
+/// Rebuilds the mesh of every currently dirty chunk.
+///
+/// Runs once per frame rather than being driven directly by individual terraforming commands, so
+/// that editing many tiles in the same chunk back-to-back (a brush stroke, heightmap generation)
+/// results in one rebuild per touched chunk rather than one per tile.
+pub(crate) fn rebuild_dirty_chunk_meshes(world: &mut World) {
+    let map_geometry = world.resource::<MapGeometry>();
+    let dirty_chunks: Vec<ChunkId> = map_geometry.dirty_chunks().collect();
+
+    for chunk_id in dirty_chunks {
+        RebuildChunkMeshCommand { chunk_id }.write(world);
     }
 }
@@ -0,0 +1,20 @@
+//! Terrain generation, terraforming, and rendering.
+
+use bevy::prelude::{App, Plugin, Update};
+
+pub(crate) mod commands;
+
+use commands::{rebuild_dirty_chunk_meshes, HeightLimits};
+
+/// Sets up terrain generation, terraforming, and rendering.
+pub(crate) struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HeightLimits>()
+            // Chunk meshes are only marked dirty by terraforming, spawning and height-override
+            // commands; this system
+            // is what actually drains that queue and rebuilds them, once per frame.
+            .add_systems(Update, rebuild_dirty_chunk_meshes);
+    }
+}